@@ -1,4 +1,10 @@
 //! Command line options
+use crate::command;
+use crate::command::avg_nn::AvgNN;
+use crate::command::jaccard::Jaccard;
+use crate::command::ripley::Ripley;
+use crate::command::stats::Stats;
+use crate::io::csv::{CsvOptions, Delimiter};
 use std::fmt;
 use std::str::FromStr;
 use structopt::StructOpt;
@@ -20,10 +26,91 @@ pub struct Cli {
     #[structopt(short, long, value_name = "path")]
     output: String,
 
+    /// Column names holding point coordinates, in order
+    #[structopt(short, long, value_name = "name", use_delimiter = true)]
+    columns: Vec<String>,
+    /// Optional column holding point IDs
+    #[structopt(long, value_name = "name")]
+    id: Option<String>,
+    /// CSV field delimiter, or `auto` to detect it from the file
+    #[structopt(long, default_value = ";")]
+    delimiter: String,
+    /// String used to mark missing values
+    #[structopt(long, default_value = "NA")]
+    no_data: String,
+    /// Treat the input as headerless; `--columns`/`--id` are then 0-based column indices
+    #[structopt(long)]
+    no_header: bool,
+    /// Decimal separator used in numeric fields (e.g. `,` for European-style data)
+    #[structopt(long, default_value = ".")]
+    decimal_separator: char,
+
     #[structopt(subcommand)]
     cmd: Command,
 }
 
+impl Cli {
+    /// Assembles the `CsvOptions` configured via the shared CSV flags.
+    pub fn csv_options(&self) -> CsvOptions {
+        let delimiter = if self.delimiter.eq_ignore_ascii_case("auto") {
+            Delimiter::Auto
+        } else {
+            Delimiter::Fixed(self.delimiter.bytes().next().unwrap_or(b';'))
+        };
+        CsvOptions::new(
+            delimiter,
+            self.no_data.clone(),
+            !self.no_header,
+            self.decimal_separator,
+        )
+    }
+
+    /// Builds the `command::Command` selected by the subcommand, ready to `execute`.
+    pub fn build_command(&self) -> Box<dyn command::Command> {
+        match &self.cmd {
+            Command::Jaccard { reference } => Box::new(Jaccard::new(
+                self.pattern.clone(),
+                reference.clone(),
+                self.output.clone(),
+                self.columns.clone(),
+                self.id.clone(),
+                self.csv_options(),
+            )),
+            Command::AvgNN {} => Box::new(AvgNN::new(
+                self.pattern.clone(),
+                self.output.clone(),
+                self.columns.clone(),
+                self.id.clone(),
+                self.csv_options(),
+            )),
+            Command::Stats {} => Box::new(Stats::new(
+                self.pattern.clone(),
+                self.output.clone(),
+                self.columns.clone(),
+                self.csv_options(),
+            )),
+            Command::Ripley {
+                radii,
+                area,
+                no_edge_correction,
+                simulations,
+                seed,
+            } => Box::new(Ripley::new(
+                self.pattern.clone(),
+                self.output.clone(),
+                self.columns.clone(),
+                self.id.clone(),
+                self.csv_options(),
+                radii.clone(),
+                *area,
+                !no_edge_correction,
+                *simulations,
+                *seed,
+            )),
+        }
+    }
+}
+
 #[derive(StructOpt)]
 enum Command {
     /// Jaccard similarity between two sets of points
@@ -35,6 +122,26 @@ enum Command {
     /// Average nearest neighbor distance of a set of points
     #[structopt(name = "avg-nn")]
     AvgNN {},
+    /// Per-dimension descriptive statistics (count, mean, stddev, min, max)
+    Stats {},
+    /// Ripley's K/L-function, with an optional Monte Carlo CSR envelope
+    Ripley {
+        /// Radii at which to evaluate K(r) and L(r)
+        #[structopt(short, long, value_name = "r", use_delimiter = true)]
+        radii: Vec<f64>,
+        /// Study-area measure. Computed from the points' bounding box if omitted
+        #[structopt(long, value_name = "area")]
+        area: Option<f64>,
+        /// Disable the translation edge-correction (all weights are then 1.0)
+        #[structopt(long)]
+        no_edge_correction: bool,
+        /// Number of complete-spatial-randomness simulations for the L(r) envelope
+        #[structopt(long, value_name = "n", default_value = "0")]
+        simulations: u32,
+        /// Seed for the CSR simulation RNG, for reproducible envelopes
+        #[structopt(long, default_value = "42")]
+        seed: u64,
+    },
 }
 
 impl FromStr for Cli {
@@ -0,0 +1,79 @@
+//! Average nearest-neighbor distance command
+use crate::command::{Command, CommandError};
+use crate::data::kdtree::KdTree;
+use crate::data::point::{PointCollection, Points};
+use crate::io::csv::{CsvOptions, CsvPointReader, CsvTableWriter};
+use crate::io::{PointReader, ResultTable, TableWriter};
+use std::path::PathBuf;
+
+/// Computes the average distance from each point to its nearest neighbor, i.e.
+/// the mean over all points of the distance to the closest other point.
+///
+/// Backed by a `KdTree`, so this is roughly O(n log n) rather than the O(n²)
+/// a brute-force scan would need.
+pub struct AvgNN {
+    pattern: String,
+    output: String,
+    columns: Vec<String>,
+    id_column: Option<String>,
+    csv_options: CsvOptions,
+}
+
+impl AvgNN {
+    pub fn new(
+        pattern: String,
+        output: String,
+        columns: Vec<String>,
+        id_column: Option<String>,
+        csv_options: CsvOptions,
+    ) -> Self {
+        AvgNN {
+            pattern,
+            output,
+            columns,
+            id_column,
+            csv_options,
+        }
+    }
+}
+
+impl Command for AvgNN {
+    fn execute(&mut self) -> Result<(), CommandError> {
+        let columns: Vec<&str> = self.columns.iter().map(|c| c.as_str()).collect();
+        let reader = CsvPointReader::new(&columns, self.id_column.as_deref(), self.csv_options.clone());
+        let collection: PointCollection<f64> = reader
+            .read(&PathBuf::from(&self.pattern))
+            .map_err(|e| CommandError(format!("Failed to read '{}': {}", self.pattern, e)))?;
+
+        // Points with a NaN coordinate can't be ordered along a k-d tree axis,
+        // so they are dropped before building the tree.
+        let dim = collection.points().dim();
+        let mut valid = Points::empty(dim);
+        for point in collection.points().iter() {
+            if !point.iter().any(|v| v.is_nan()) {
+                valid.push(point);
+            }
+        }
+
+        let tree = KdTree::new(&valid);
+        let mut sum = 0.0;
+        let mut count = 0usize;
+        for i in 0..valid.len() {
+            let (_, squared_dist) = tree.nearest(valid.get(i), Some(i));
+            if squared_dist.is_finite() {
+                sum += squared_dist.sqrt();
+                count += 1;
+            }
+        }
+        let avg = if count == 0 { 0.0 } else { sum / count as f64 };
+
+        let mut table = ResultTable::new(vec!["avg_nn".to_string()]);
+        table.push_row(vec![format!("{}", avg)]);
+
+        let writer = CsvTableWriter::new(self.csv_options.clone());
+        let path = PathBuf::from(format!("{}.csv", self.output));
+        writer
+            .write(&path, &table)
+            .map_err(|e| CommandError(format!("Failed to write '{:?}': {}", path, e)))
+    }
+}
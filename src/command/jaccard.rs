@@ -0,0 +1,83 @@
+//! Jaccard similarity command
+use crate::command::{Command, CommandError};
+use crate::data::point::PointCollection;
+use crate::io::csv::{CsvOptions, CsvPointReader, CsvTableWriter};
+use crate::io::{PointReader, ResultTable, TableWriter};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// Computes the Jaccard similarity between two sets of points, i.e. the size of
+/// their intersection divided by the size of their union. Points are identified
+/// by their ID column if one is configured, or by their coordinates otherwise.
+pub struct Jaccard {
+    pattern: String,
+    reference: String,
+    output: String,
+    columns: Vec<String>,
+    id_column: Option<String>,
+    csv_options: CsvOptions,
+}
+
+impl Jaccard {
+    pub fn new(
+        pattern: String,
+        reference: String,
+        output: String,
+        columns: Vec<String>,
+        id_column: Option<String>,
+        csv_options: CsvOptions,
+    ) -> Self {
+        Jaccard {
+            pattern,
+            reference,
+            output,
+            columns,
+            id_column,
+            csv_options,
+        }
+    }
+
+    fn read(&self, path: &str) -> Result<PointCollection<f64>, CommandError> {
+        let columns: Vec<&str> = self.columns.iter().map(|c| c.as_str()).collect();
+        let reader = CsvPointReader::new(&columns, self.id_column.as_deref(), self.csv_options.clone());
+        reader
+            .read(&PathBuf::from(path))
+            .map_err(|e| CommandError(format!("Failed to read '{}': {}", path, e)))
+    }
+
+    /// Builds the set of identifying keys for a point collection: IDs if present,
+    /// otherwise the point's own coordinates.
+    fn keys(points: &PointCollection<f64>) -> HashSet<String> {
+        match points.ids() {
+            Some(ids) => ids.iter().cloned().collect(),
+            None => points.points().iter().map(|p| format!("{:?}", p)).collect(),
+        }
+    }
+}
+
+impl Command for Jaccard {
+    fn execute(&mut self) -> Result<(), CommandError> {
+        let points = self.read(&self.pattern)?;
+        let reference = self.read(&self.reference)?;
+
+        let a = Self::keys(&points);
+        let b = Self::keys(&reference);
+
+        let intersection = a.intersection(&b).count();
+        let union = a.union(&b).count();
+        let jaccard = if union == 0 {
+            0.0
+        } else {
+            intersection as f64 / union as f64
+        };
+
+        let mut table = ResultTable::new(vec!["jaccard".to_string()]);
+        table.push_row(vec![format!("{}", jaccard)]);
+
+        let writer = CsvTableWriter::new(self.csv_options.clone());
+        let path = PathBuf::from(format!("{}.csv", self.output));
+        writer
+            .write(&path, &table)
+            .map_err(|e| CommandError(format!("Failed to write '{:?}': {}", path, e)))
+    }
+}
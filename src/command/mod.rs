@@ -1,6 +1,11 @@
 //! Command traits and implementations
 use std::fmt;
 
+pub mod avg_nn;
+pub mod jaccard;
+pub mod ripley;
+pub mod stats;
+
 pub trait Command {
     fn execute(&mut self) -> Result<(), CommandError> {
         Ok(())
@@ -0,0 +1,241 @@
+//! Ripley's K/L-function command with Monte Carlo CSR envelopes
+use crate::command::{Command, CommandError};
+use crate::data::point::PointCollection;
+use crate::io::csv::{CsvOptions, CsvPointReader, CsvTableWriter};
+use crate::io::{PointReader, ResultTable, TableWriter};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::f64::consts::PI;
+use std::path::PathBuf;
+
+/// Computes Ripley's K-function and its variance-stabilized L-transform for a
+/// 2-D point collection. `L(r) > 0` indicates clustering at radius `r`,
+/// `L(r) < 0` indicates dispersion.
+///
+/// Optionally runs Monte Carlo complete-spatial-randomness (CSR) simulations
+/// to derive a per-radius `L(r)` envelope to judge significance against.
+pub struct Ripley {
+    pattern: String,
+    output: String,
+    columns: Vec<String>,
+    id_column: Option<String>,
+    csv_options: CsvOptions,
+    radii: Vec<f64>,
+    area: Option<f64>,
+    edge_correction: bool,
+    simulations: u32,
+    seed: u64,
+}
+
+impl Ripley {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        pattern: String,
+        output: String,
+        columns: Vec<String>,
+        id_column: Option<String>,
+        csv_options: CsvOptions,
+        radii: Vec<f64>,
+        area: Option<f64>,
+        edge_correction: bool,
+        simulations: u32,
+        seed: u64,
+    ) -> Self {
+        Ripley {
+            pattern,
+            output,
+            columns,
+            id_column,
+            csv_options,
+            radii,
+            area,
+            edge_correction,
+            simulations,
+            seed,
+        }
+    }
+
+    fn read_points(&self) -> Result<Vec<[f64; 2]>, CommandError> {
+        let columns: Vec<&str> = self.columns.iter().map(|c| c.as_str()).collect();
+        let reader = CsvPointReader::new(&columns, self.id_column.as_deref(), self.csv_options.clone());
+        let collection: PointCollection<f64> = reader
+            .read(&PathBuf::from(&self.pattern))
+            .map_err(|e| CommandError(format!("Failed to read '{}': {}", self.pattern, e)))?;
+
+        if collection.points().dim() != 2 {
+            return Err(CommandError(format!(
+                "ripley requires exactly 2 coordinate columns, got {}",
+                collection.points().dim()
+            )));
+        }
+
+        Ok(collection
+            .points()
+            .iter()
+            .filter(|p| !p.iter().any(|v| v.is_nan()))
+            .map(|p| [p[0], p[1]])
+            .collect())
+    }
+}
+
+/// Returns `(min_x, max_x, min_y, max_y)` over the given points.
+fn bounding_box(points: &[[f64; 2]]) -> (f64, f64, f64, f64) {
+    let mut min_x = f64::INFINITY;
+    let mut max_x = f64::NEG_INFINITY;
+    let mut min_y = f64::INFINITY;
+    let mut max_y = f64::NEG_INFINITY;
+    for p in points {
+        min_x = min_x.min(p[0]);
+        max_x = max_x.max(p[0]);
+        min_y = min_y.min(p[1]);
+        max_y = max_y.max(p[1]);
+    }
+    (min_x, max_x, min_y, max_y)
+}
+
+/// Translation edge-correction weight (Ohser 1983): the inverse fraction of
+/// the study window that still overlaps itself after being shifted by the
+/// vector between the two points.
+fn translation_weight(width: f64, height: f64, dx: f64, dy: f64) -> f64 {
+    let overlap = (width - dx.abs()).max(f64::EPSILON) * (height - dy.abs()).max(f64::EPSILON);
+    (width * height) / overlap
+}
+
+/// Evaluates `K(r) = (A / n²) * sum_{i≠j} w_ij * I(d_ij <= r)` for every radius.
+fn k_function(
+    points: &[[f64; 2]],
+    radii: &[f64],
+    area: f64,
+    width: f64,
+    height: f64,
+    edge_correction: bool,
+) -> Vec<f64> {
+    let n = points.len();
+    let mut sums = vec![0.0; radii.len()];
+    for i in 0..n {
+        for j in 0..n {
+            if i == j {
+                continue;
+            }
+            let dx = points[i][0] - points[j][0];
+            let dy = points[i][1] - points[j][1];
+            let d = (dx * dx + dy * dy).sqrt();
+            let w = if edge_correction {
+                translation_weight(width, height, dx, dy)
+            } else {
+                1.0
+            };
+            for (k, &r) in radii.iter().enumerate() {
+                if d <= r {
+                    sums[k] += w;
+                }
+            }
+        }
+    }
+    let factor = area / (n as f64 * n as f64);
+    sums.iter().map(|s| s * factor).collect()
+}
+
+/// `L(r) = sqrt(K(r) / pi) - r`
+fn l_transform(k: &[f64], radii: &[f64]) -> Vec<f64> {
+    k.iter().zip(radii).map(|(&k, &r)| (k / PI).sqrt() - r).collect()
+}
+
+impl Command for Ripley {
+    fn execute(&mut self) -> Result<(), CommandError> {
+        if self.radii.is_empty() {
+            return Err(CommandError(
+                "ripley requires at least one radius via --radii".to_string(),
+            ));
+        }
+
+        let points = self.read_points()?;
+        if points.len() < 2 {
+            return Err(CommandError("ripley requires at least two points".to_string()));
+        }
+
+        let (min_x, max_x, min_y, max_y) = bounding_box(&points);
+        let width = max_x - min_x;
+        let height = max_y - min_y;
+        let area = self.area.unwrap_or(width * height);
+
+        let k = k_function(&points, &self.radii, area, width, height, self.edge_correction);
+        let l = l_transform(&k, &self.radii);
+
+        let envelope = if self.simulations > 0 {
+            let mut rng = StdRng::seed_from_u64(self.seed);
+            let n = points.len();
+            let mut lo = vec![f64::INFINITY; self.radii.len()];
+            let mut hi = vec![f64::NEG_INFINITY; self.radii.len()];
+            for _ in 0..self.simulations {
+                let sim: Vec<[f64; 2]> = (0..n)
+                    .map(|_| [rng.gen_range(min_x..=max_x), rng.gen_range(min_y..=max_y)])
+                    .collect();
+                let sim_k = k_function(&sim, &self.radii, area, width, height, self.edge_correction);
+                let sim_l = l_transform(&sim_k, &self.radii);
+                for (i, &l) in sim_l.iter().enumerate() {
+                    lo[i] = lo[i].min(l);
+                    hi[i] = hi[i].max(l);
+                }
+            }
+            Some((lo, hi))
+        } else {
+            None
+        };
+
+        let mut header = vec!["r".to_string(), "K".to_string(), "L".to_string()];
+        if envelope.is_some() {
+            header.push("L_lower".to_string());
+            header.push("L_upper".to_string());
+        }
+        let mut table = ResultTable::new(header);
+        for i in 0..self.radii.len() {
+            let mut row = vec![
+                format!("{}", self.radii[i]),
+                format!("{}", k[i]),
+                format!("{}", l[i]),
+            ];
+            if let Some((lo, hi)) = &envelope {
+                row.push(format!("{}", lo[i]));
+                row.push(format!("{}", hi[i]));
+            }
+            table.push_row(row);
+        }
+
+        let writer = CsvTableWriter::new(self.csv_options.clone());
+        let path = PathBuf::from(format!("{}.csv", self.output));
+        writer
+            .write(&path, &table)
+            .map_err(|e| CommandError(format!("Failed to write '{:?}': {}", path, e)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::ripley::{k_function, l_transform, translation_weight};
+
+    #[test]
+    fn l_transform_of_csr_k_is_zero() {
+        // For a homogeneous Poisson process, K(r) = pi * r^2, so L(r) should vanish.
+        let radii = vec![1.0, 2.0, 3.0];
+        let k: Vec<f64> = radii.iter().map(|r| std::f64::consts::PI * r * r).collect();
+        for l in l_transform(&k, &radii) {
+            assert!(l.abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn translation_weight_is_one_for_zero_offset() {
+        assert_eq!(translation_weight(10.0, 10.0, 0.0, 0.0), 1.0);
+    }
+
+    #[test]
+    fn k_function_counts_neighbors_within_radius() {
+        // Two points 1 apart: for r >= 1 each sees the other once.
+        let points = vec![[0.0, 0.0], [1.0, 0.0]];
+        let radii = vec![0.5, 1.0];
+        let k = k_function(&points, &radii, 4.0, 2.0, 2.0, false);
+        assert_eq!(k[0], 0.0);
+        assert!(k[1] > 0.0);
+    }
+}
@@ -0,0 +1,158 @@
+//! Streaming descriptive-statistics command
+use crate::command::{Command, CommandError};
+use crate::io::csv::{CsvOptions, CsvTableWriter};
+use crate::io::{ResultTable, TableWriter};
+use csv::ReaderBuilder;
+use std::path::{Path, PathBuf};
+
+/// Running summary statistics for a single dimension, updated one value at a
+/// time via Welford's online algorithm so the full column never has to be
+/// held in memory.
+#[derive(Debug, Clone, Copy)]
+struct Welford {
+    count: u64,
+    mean: f64,
+    m2: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Welford {
+    fn new() -> Self {
+        Welford {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+    fn push(&mut self, x: f64) {
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        let delta2 = x - self.mean;
+        self.m2 += delta * delta2;
+        if x < self.min {
+            self.min = x;
+        }
+        if x > self.max {
+            self.max = x;
+        }
+    }
+    fn variance(&self) -> f64 {
+        if self.count < 2 {
+            0.0
+        } else {
+            self.m2 / (self.count - 1) as f64
+        }
+    }
+    fn stddev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+}
+
+/// Computes per-dimension `count`, `mean`, `stddev`, `min` and `max` in a single
+/// pass over the input file, skipping no-data (NaN) values.
+pub struct Stats {
+    pattern: String,
+    output: String,
+    columns: Vec<String>,
+    csv_options: CsvOptions,
+}
+
+impl Stats {
+    pub fn new(pattern: String, output: String, columns: Vec<String>, csv_options: CsvOptions) -> Self {
+        Stats {
+            pattern,
+            output,
+            columns,
+            csv_options,
+        }
+    }
+}
+
+impl Command for Stats {
+    fn execute(&mut self) -> Result<(), CommandError> {
+        let path = Path::new(&self.pattern);
+        let delimiter = self
+            .csv_options
+            .resolved_delimiter(path)
+            .map_err(|e| CommandError(format!("Failed to read '{}': {}", self.pattern, e)))?;
+        let mut reader = ReaderBuilder::new()
+            .delimiter(delimiter)
+            .has_headers(self.csv_options.has_header())
+            .from_path(path)
+            .map_err(|e| CommandError(format!("Failed to read '{}': {}", self.pattern, e)))?;
+
+        let indices: Vec<usize> = if self.csv_options.has_header() {
+            let header = reader
+                .headers()
+                .map_err(|e| CommandError(format!("Failed to read '{}': {}", self.pattern, e)))?
+                .clone();
+            let header: Vec<_> = header.iter().collect();
+            self.columns
+                .iter()
+                .map(|col| {
+                    header
+                        .iter()
+                        .position(|h| h == col)
+                        .ok_or_else(|| CommandError(format!("Column {} not found.", col)))
+                })
+                .collect::<Result<_, _>>()?
+        } else {
+            self.columns
+                .iter()
+                .map(|col| {
+                    col.parse::<usize>().map_err(|_e| {
+                        CommandError(format!(
+                            "Column '{}' is not a valid 0-based index (no-header mode).",
+                            col
+                        ))
+                    })
+                })
+                .collect::<Result<_, _>>()?
+        };
+
+        let mut accumulators: Vec<Welford> = self.columns.iter().map(|_| Welford::new()).collect();
+
+        for record in reader.records() {
+            let record = record.map_err(|e| CommandError(format!("{}", e)))?;
+            for (acc, idx) in accumulators.iter_mut().zip(&indices) {
+                let value = record
+                    .get(*idx)
+                    .ok_or_else(|| CommandError(format!("Column index {} out of range for row {:?}.", idx, record)))?;
+                let parsed: f64 = self
+                    .csv_options
+                    .parse_value(value)
+                    .map_err(|e| CommandError(format!("{}", e)))?;
+                if !parsed.is_nan() {
+                    acc.push(parsed);
+                }
+            }
+        }
+
+        let mut table = ResultTable::new(
+            ["dim", "count", "mean", "stddev", "min", "max"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+        );
+        for (col, acc) in self.columns.iter().zip(&accumulators) {
+            table.push_row(vec![
+                col.clone(),
+                acc.count.to_string(),
+                format!("{}", acc.mean),
+                format!("{}", acc.stddev()),
+                format!("{}", acc.min),
+                format!("{}", acc.max),
+            ]);
+        }
+
+        let writer = CsvTableWriter::new(self.csv_options.clone());
+        let path = PathBuf::from(format!("{}.csv", self.output));
+        writer
+            .write(&path, &table)
+            .map_err(|e| CommandError(format!("Failed to write '{:?}': {}", path, e)))
+    }
+}
@@ -0,0 +1,195 @@
+//! k-d tree spatial index
+use crate::data::point::Points;
+use num_traits::Float;
+
+/// A node of the tree: a point index, the axis it splits on, and the index of
+/// its children among `KdTree::nodes` (if any).
+struct Node {
+    point: usize,
+    axis: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+/// A balanced k-d tree built over a `Points<T>` reference, supporting
+/// nearest-neighbor queries via branch-and-bound backtracking.
+///
+/// The tree is built by recursively partitioning point indices around the
+/// median along cyclically alternating dimensions (`axis = depth % dim`).
+pub struct KdTree<'a, T>
+where
+    T: Float,
+{
+    points: &'a Points<T>,
+    nodes: Vec<Node>,
+    root: Option<usize>,
+}
+
+impl<'a, T> KdTree<'a, T>
+where
+    T: Float,
+{
+    /// Builds a k-d tree over all points of the given collection.
+    pub fn new(points: &'a Points<T>) -> Self {
+        let mut indices: Vec<usize> = (0..points.len()).collect();
+        let mut nodes = Vec::with_capacity(indices.len());
+        let dim = points.dim();
+        let root = Self::build(points, &mut indices, 0, dim, &mut nodes);
+        KdTree {
+            points,
+            nodes,
+            root,
+        }
+    }
+
+    fn build(
+        points: &Points<T>,
+        indices: &mut [usize],
+        depth: usize,
+        dim: usize,
+        nodes: &mut Vec<Node>,
+    ) -> Option<usize> {
+        if indices.is_empty() {
+            return None;
+        }
+        let axis = depth % dim;
+        let mid = indices.len() / 2;
+        indices.select_nth_unstable_by(mid, |&a, &b| {
+            points.get(a)[axis].partial_cmp(&points.get(b)[axis]).unwrap()
+        });
+        let median = indices[mid];
+
+        let (left_indices, rest) = indices.split_at_mut(mid);
+        let right_indices = &mut rest[1..];
+
+        let left = Self::build(points, left_indices, depth + 1, dim, nodes);
+        let right = Self::build(points, right_indices, depth + 1, dim, nodes);
+
+        nodes.push(Node {
+            point: median,
+            axis,
+            left,
+            right,
+        });
+        Some(nodes.len() - 1)
+    }
+
+    /// Returns the index and squared distance of the nearest point to `query`,
+    /// optionally excluding one point index (e.g. the query point itself).
+    pub fn nearest(&self, query: &[T], exclude: Option<usize>) -> (usize, T) {
+        let mut best_index = usize::MAX;
+        let mut best_dist = T::infinity();
+        if let Some(root) = self.root {
+            self.search(root, query, exclude, &mut best_index, &mut best_dist);
+        }
+        (best_index, best_dist)
+    }
+
+    fn search(
+        &self,
+        node_index: usize,
+        query: &[T],
+        exclude: Option<usize>,
+        best_index: &mut usize,
+        best_dist: &mut T,
+    ) {
+        let node = &self.nodes[node_index];
+        let point = self.points.get(node.point);
+
+        if Some(node.point) != exclude {
+            let dist = squared_distance(query, point);
+            if dist < *best_dist {
+                *best_dist = dist;
+                *best_index = node.point;
+            }
+        }
+
+        let diff = query[node.axis] - point[node.axis];
+        let (near, far) = if diff < T::zero() {
+            (node.left, node.right)
+        } else {
+            (node.right, node.left)
+        };
+
+        if let Some(near) = near {
+            self.search(near, query, exclude, best_index, best_dist);
+        }
+        // Only descend into the far subtree if the splitting plane is closer
+        // than the current best match, i.e. it could still hold a closer point.
+        if let Some(far) = far {
+            if diff * diff < *best_dist {
+                self.search(far, query, exclude, best_index, best_dist);
+            }
+        }
+    }
+}
+
+fn squared_distance<T>(a: &[T], b: &[T]) -> T
+where
+    T: Float,
+{
+    a.iter()
+        .zip(b)
+        .map(|(&x, &y)| (x - y) * (x - y))
+        .fold(T::zero(), |acc, d| acc + d)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::data::kdtree::KdTree;
+    use crate::data::point::Points;
+
+    #[test]
+    fn nearest_excludes_self() {
+        let points = Points::from_rows(&[
+            vec![0.0, 0.0],
+            vec![1.0, 0.0],
+            vec![0.0, 5.0],
+            vec![10.0, 10.0],
+        ])
+        .unwrap();
+        let tree = KdTree::new(&points);
+
+        let (index, dist) = tree.nearest(points.get(0), Some(0));
+        assert_eq!(index, 1);
+        assert_eq!(dist, 1.0);
+    }
+
+    #[test]
+    fn nearest_matches_brute_force() {
+        let rows: Vec<Vec<f64>> = (0..50)
+            .map(|i: i64| vec![((i * 37) % 23) as f64, ((i * 13) % 17) as f64])
+            .collect();
+        let points = Points::from_rows(&rows).unwrap();
+        let tree = KdTree::new(&points);
+
+        for i in 0..points.len() {
+            let query = points.get(i);
+            let (tree_index, tree_dist) = tree.nearest(query, Some(i));
+
+            let mut brute_dist = f64::INFINITY;
+            let mut brute_index = usize::MAX;
+            for j in 0..points.len() {
+                if i == j {
+                    continue;
+                }
+                let other = points.get(j);
+                let d: f64 = query
+                    .iter()
+                    .zip(other)
+                    .map(|(x, y)| (x - y) * (x - y))
+                    .sum();
+                if d < brute_dist {
+                    brute_dist = d;
+                    brute_index = j;
+                }
+            }
+
+            assert_eq!(
+                tree_dist, brute_dist,
+                "mismatched nearest-neighbor distance for point {} (tree picked {}, brute picked {})",
+                i, tree_index, brute_index
+            );
+        }
+    }
+}
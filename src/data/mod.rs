@@ -0,0 +1,3 @@
+//! Point pattern data structures
+pub mod kdtree;
+pub mod point;
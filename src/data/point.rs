@@ -23,6 +23,9 @@ where
     pub fn points(&self) -> &Points<T> {
         &self.points
     }
+    pub fn ids(&self) -> Option<&Vec<String>> {
+        self.ids.as_ref()
+    }
 }
 
 impl<T> PointCollection<T>
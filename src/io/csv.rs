@@ -1,34 +1,128 @@
 //! CSV file IO
 use crate::data::point::{PointCollection, PointConstructionError, Points};
-use crate::io::PointReader;
-use csv::{ReaderBuilder, StringRecord};
+use crate::io::{PointReader, PointWriter, ResultTable, TableWriter};
+use csv::{ReaderBuilder, StringRecord, WriterBuilder};
 use num_traits::Float;
-use std::path::PathBuf;
-use std::{fmt, io};
+use std::path::{Path, PathBuf};
+use std::{fmt, fs, io};
 
 /// PCsv result type
 type CsvResult<T> = Result<T, CsvError>;
 
+/// Candidate delimiters considered when sniffing, in order of preference on ties.
+const CANDIDATE_DELIMITERS: [u8; 4] = [b';', b',', b'\t', b'|'];
+/// Number of leading lines inspected when sniffing the delimiter.
+const SNIFF_LINES: usize = 5;
+
+/// The CSV field delimiter: either fixed, or auto-detected per file.
+#[derive(Clone, Debug)]
+pub enum Delimiter {
+    Fixed(u8),
+    Auto,
+}
+
 /// Csv file options
 #[derive(Clone, Debug)]
 pub struct CsvOptions {
-    delimiter: u8,
+    delimiter: Delimiter,
     no_data: String,
+    has_header: bool,
+    decimal_separator: char,
 }
 impl CsvOptions {
-    pub fn new(delimiter: u8, no_data: String) -> Self {
-        CsvOptions { delimiter, no_data }
+    pub fn new(delimiter: Delimiter, no_data: String, has_header: bool, decimal_separator: char) -> Self {
+        CsvOptions {
+            delimiter,
+            no_data,
+            has_header,
+            decimal_separator,
+        }
+    }
+    pub fn no_data(&self) -> &str {
+        &self.no_data
+    }
+    pub fn has_header(&self) -> bool {
+        self.has_header
+    }
+    pub fn decimal_separator(&self) -> char {
+        self.decimal_separator
+    }
+    /// Resolves the delimiter for the given file, sniffing it from the file's
+    /// first few lines if set to `Delimiter::Auto`.
+    pub fn resolved_delimiter(&self, file: &Path) -> CsvResult<u8> {
+        match self.delimiter {
+            Delimiter::Fixed(d) => Ok(d),
+            Delimiter::Auto => sniff_delimiter(file, self.decimal_separator),
+        }
+    }
+    /// Resolves the delimiter without inspecting a file, for contexts (e.g.
+    /// writing output) where there is nothing to sniff.
+    pub fn delimiter_or_default(&self) -> u8 {
+        match self.delimiter {
+            Delimiter::Fixed(d) => d,
+            Delimiter::Auto => b';',
+        }
+    }
+    /// Parses a value, honoring the configured no-data marker and decimal separator.
+    pub fn parse_value<T>(&self, str: &str) -> CsvResult<T>
+    where
+        T: Float,
+    {
+        if str == self.no_data {
+            return Ok(T::nan());
+        }
+        let normalized;
+        let str = if self.decimal_separator != '.' {
+            normalized = str.replace(self.decimal_separator, ".");
+            normalized.as_str()
+        } else {
+            str
+        };
+        T::from_str_radix(str, 10)
+            .map_err(|_e| CsvError::ParseError(format!("Unable to parse value '{}' to float.", str)))
     }
 }
 impl Default for CsvOptions {
     fn default() -> Self {
         CsvOptions {
-            delimiter: b';',
+            delimiter: Delimiter::Fixed(b';'),
             no_data: "NA".to_string(),
+            has_header: true,
+            decimal_separator: '.',
         }
     }
 }
 
+/// Picks the delimiter among [`CANDIDATE_DELIMITERS`] whose occurrence count is
+/// most consistent across the file's first [`SNIFF_LINES`] lines, preferring
+/// the one with the most fields on ties. The configured `decimal_separator` is
+/// excluded from consideration, so e.g. European `1,5`-style data doesn't make
+/// `,` look like a more consistent field delimiter than the real one.
+fn sniff_delimiter(file: &Path, decimal_separator: char) -> CsvResult<u8> {
+    let reader = io::BufReader::new(fs::File::open(file)?);
+    let sample: Vec<String> = io::BufRead::lines(reader)
+        .take(SNIFF_LINES)
+        .collect::<io::Result<_>>()?;
+
+    let mut best = CANDIDATE_DELIMITERS[0];
+    let mut best_count = 0usize;
+    for &delimiter in &CANDIDATE_DELIMITERS {
+        if delimiter as char == decimal_separator {
+            continue;
+        }
+        let counts: Vec<usize> = sample
+            .iter()
+            .map(|line| line.matches(delimiter as char).count())
+            .collect();
+        let consistent = !counts.is_empty() && counts[0] > 0 && counts.iter().all(|&c| c == counts[0]);
+        if consistent && counts[0] > best_count {
+            best = delimiter;
+            best_count = counts[0];
+        }
+    }
+    Ok(best)
+}
+
 /// Reader for CSV point collection files
 pub struct CsvPointReader {
     columns: Vec<String>,
@@ -39,19 +133,30 @@ impl CsvPointReader {
     pub fn new(columns: &[&str], id_column: Option<&str>, options: CsvOptions) -> Self {
         CsvPointReader {
             columns: columns.iter().map(|c| c.to_string()).collect(),
-            id_column: id_column.and_then(|c| Some(c.to_string())),
+            id_column: id_column.map(|c| c.to_string()),
             options,
         }
     }
+    /// Resolves a configured column to its index. With a header, `column` is
+    /// looked up by name; in `--no-header` mode, `column` is itself parsed as
+    /// a 0-based index.
     fn column_index(
         &self,
-        header: &[&str],
+        header: &Option<Vec<&str>>,
         column: &str,
     ) -> std::result::Result<usize, ColumnIndexError> {
-        header
-            .iter()
-            .position(|n| &column == n)
-            .ok_or(ColumnIndexError(format!("Column {} not found.", column)))
+        match header {
+            Some(header) => header
+                .iter()
+                .position(|n| &column == n)
+                .ok_or_else(|| ColumnIndexError(format!("Column {} not found.", column))),
+            None => column.parse::<usize>().map_err(|_e| {
+                ColumnIndexError(format!(
+                    "Column '{}' is not a valid 0-based index (no-header mode).",
+                    column
+                ))
+            }),
+        }
     }
 }
 
@@ -63,23 +168,32 @@ where
 
     /// Reads a CSV file and parses it into a PointCollection
     fn read(&self, file: &PathBuf) -> CsvResult<PointCollection<T>> {
-        let no_data = &self.options.no_data;
+        let delimiter = self.options.resolved_delimiter(file)?;
 
         // Read csv
         let mut reader = ReaderBuilder::new()
-            .delimiter(self.options.delimiter)
+            .delimiter(delimiter)
+            .has_headers(self.options.has_header)
             .from_path(file)?;
-        let header: StringRecord = reader.headers()?.clone();
-        let header: Vec<_> = header.iter().collect();
+
+        let owned_header: Option<Vec<String>> = if self.options.has_header {
+            let header: StringRecord = reader.headers()?.clone();
+            Some(header.iter().map(|s| s.to_string()).collect())
+        } else {
+            None
+        };
+        let header: Option<Vec<&str>> = owned_header
+            .as_ref()
+            .map(|h| h.iter().map(|s| s.as_str()).collect());
 
         let id_index = match &self.id_column {
-            Some(col) => Some(self.column_index(&header[..], &col)?),
+            Some(col) => Some(self.column_index(&header, col)?),
             None => None,
         };
 
         let mut col_indices = vec![];
         for col in &self.columns {
-            col_indices.push(self.column_index(&header[..], col)?)
+            col_indices.push(self.column_index(&header, col)?)
         }
 
         let mut ids = vec![];
@@ -87,25 +201,16 @@ where
         for record in reader.records() {
             let rec = record?;
             if let Some(id_idx) = id_index {
-                ids.push(rec.get(id_idx).unwrap().to_string());
+                let id = rec
+                    .get(id_idx)
+                    .ok_or_else(|| ColumnIndexError(format!("Column index {} out of range for row {:?}.", id_idx, rec)))?;
+                ids.push(id.to_string());
             }
             for col in &col_indices {
-                let str = rec.get(*col).unwrap();
-                let val = if str == no_data {
-                    T::nan()
-                } else {
-                    match T::from_str_radix(str, 10) {
-                        Ok(v) => v,
-                        Err(_e) => {
-                            return Err(CsvError::ParseError(format!(
-                                "Unable to parse value '{}' to float.",
-                                str
-                            )))
-                        }
-                    }
-                };
-
-                data.push(val);
+                let str = rec
+                    .get(*col)
+                    .ok_or_else(|| ColumnIndexError(format!("Column index {} out of range for row {:?}.", col, rec)))?;
+                data.push(self.options.parse_value(str)?);
             }
         }
 
@@ -116,6 +221,91 @@ where
     }
 }
 
+/// Writer for CSV point collection files, mirroring `CsvPointReader`
+pub struct CsvPointWriter {
+    columns: Vec<String>,
+    id_column: Option<String>,
+    options: CsvOptions,
+}
+impl CsvPointWriter {
+    pub fn new(columns: &[&str], id_column: Option<&str>, options: CsvOptions) -> Self {
+        CsvPointWriter {
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            id_column: id_column.map(|c| c.to_string()),
+            options,
+        }
+    }
+}
+
+impl<T> PointWriter<T> for CsvPointWriter
+where
+    T: Float + fmt::Display,
+{
+    type ErrorType = CsvError;
+
+    /// Writes a PointCollection to a CSV file
+    fn write(&self, file: &PathBuf, points: &PointCollection<T>) -> CsvResult<()> {
+        let no_data = &self.options.no_data;
+
+        let mut writer = WriterBuilder::new()
+            .delimiter(self.options.delimiter_or_default())
+            .from_path(file)?;
+
+        let mut header = vec![];
+        if let Some(id_column) = &self.id_column {
+            header.push(id_column.clone());
+        }
+        header.extend(self.columns.iter().cloned());
+        writer.write_record(&header)?;
+
+        let ids = points.ids();
+        for (i, row) in points.points().iter().enumerate() {
+            let mut record = vec![];
+            if self.id_column.is_some() {
+                let id = ids.and_then(|ids| ids.get(i)).cloned().unwrap_or_default();
+                record.push(id);
+            }
+            for val in row {
+                record.push(if val.is_nan() {
+                    no_data.clone()
+                } else {
+                    format!("{}", val)
+                });
+            }
+            writer.write_record(&record)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Writer for generic result tables (e.g. descriptive statistics, similarity indices)
+pub struct CsvTableWriter {
+    options: CsvOptions,
+}
+impl CsvTableWriter {
+    pub fn new(options: CsvOptions) -> Self {
+        CsvTableWriter { options }
+    }
+}
+
+impl TableWriter for CsvTableWriter {
+    type ErrorType = CsvError;
+
+    /// Writes a ResultTable to a CSV file
+    fn write(&self, file: &PathBuf, table: &ResultTable) -> CsvResult<()> {
+        let mut writer = WriterBuilder::new()
+            .delimiter(self.options.delimiter_or_default())
+            .from_path(file)?;
+        writer.write_record(table.header())?;
+        for row in table.rows() {
+            writer.write_record(row)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
 /// Error type for different possible errors during CSV to PointCollection reading.
 #[derive(Debug)]
 pub enum CsvError {
@@ -169,11 +359,106 @@ impl fmt::Display for ColumnIndexError {
 
 #[cfg(test)]
 mod test {
-    use crate::data::point::PointCollection;
-    use crate::io::csv::{CsvOptions, CsvPointReader};
-    use crate::io::PointReader;
+    use crate::data::point::{PointCollection, Points};
+    use crate::io::csv::{CsvOptions, CsvPointReader, CsvPointWriter, CsvTableWriter, Delimiter};
+    use crate::io::{PointReader, PointWriter, ResultTable, TableWriter};
     use std::path::PathBuf;
 
+    #[test]
+    fn write_and_read_csv() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("ppa-write-and-read.csv");
+
+        let points = PointCollection::new(
+            Points::from_rows(&[vec![0.0, 1.0], vec![2.0, f32::NAN]]).unwrap(),
+            Some(vec!["a".to_string(), "b".to_string()]),
+        )
+        .unwrap();
+
+        let writer = CsvPointWriter::new(&["X", "Y"], Some("ID"), CsvOptions::default());
+        writer.write(&path, &points).unwrap();
+
+        let reader = CsvPointReader::new(&["X", "Y"], Some("ID"), CsvOptions::default());
+        let read_back: PointCollection<f32> = reader.read(&path).unwrap();
+
+        assert_eq!(read_back.points().len(), 2);
+        assert_eq!(read_back.points().get(0), &[0.0, 1.0]);
+        assert!(read_back.points().get(1)[1].is_nan());
+        assert_eq!(read_back.ids().unwrap(), &vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn write_table() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("ppa-write-table.csv");
+
+        let mut table = ResultTable::new(vec!["dim".to_string(), "mean".to_string()]);
+        table.push_row(vec!["x".to_string(), "1.5".to_string()]);
+
+        let writer = CsvTableWriter::new(CsvOptions::default());
+        writer.write(&path, &table).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("dim;mean"));
+        assert!(content.contains("x;1.5"));
+    }
+
+    #[test]
+    fn read_headerless_csv() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("ppa-headerless.csv");
+        std::fs::write(&path, "p1;0.0;1.0\np2;2.0;NA\n").unwrap();
+
+        let options = CsvOptions::new(Delimiter::Fixed(b';'), "NA".to_string(), false, '.');
+        let reader = CsvPointReader::new(&["1", "2"], Some("0"), options);
+        let points: PointCollection<f64> = reader.read(&path).unwrap();
+
+        assert_eq!(points.points().get(0), &[0.0, 1.0]);
+        assert!(points.points().get(1)[1].is_nan());
+        assert_eq!(points.ids().unwrap(), &vec!["p1".to_string(), "p2".to_string()]);
+    }
+
+    #[test]
+    fn read_csv_with_sniffed_delimiter() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("ppa-sniffed.csv");
+        std::fs::write(&path, "X,Y\n0.0,1.0\n2.0,3.0\n").unwrap();
+
+        let options = CsvOptions::new(Delimiter::Auto, "NA".to_string(), true, '.');
+        let reader = CsvPointReader::new(&["X", "Y"], None, options);
+        let points: PointCollection<f64> = reader.read(&path).unwrap();
+
+        assert_eq!(points.points().get(0), &[0.0, 1.0]);
+        assert_eq!(points.points().get(1), &[2.0, 3.0]);
+    }
+
+    #[test]
+    fn sniff_prefers_real_delimiter_over_decimal_separator() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("ppa-sniff-vs-decimal.csv");
+        std::fs::write(&path, "0,5;1,5\n2,5;3,5\n").unwrap();
+
+        let options = CsvOptions::new(Delimiter::Auto, "NA".to_string(), false, ',');
+        let reader = CsvPointReader::new(&["0", "1"], None, options);
+        let points: PointCollection<f64> = reader.read(&path).unwrap();
+
+        assert_eq!(points.points().get(0), &[0.5, 1.5]);
+        assert_eq!(points.points().get(1), &[2.5, 3.5]);
+    }
+
+    #[test]
+    fn read_csv_with_decimal_comma() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("ppa-decimal-comma.csv");
+        std::fs::write(&path, "X;Y\n0,5;1,5\n").unwrap();
+
+        let options = CsvOptions::new(Delimiter::Fixed(b';'), "NA".to_string(), true, ',');
+        let reader = CsvPointReader::new(&["X", "Y"], None, options);
+        let points: PointCollection<f64> = reader.read(&path).unwrap();
+
+        assert_eq!(points.points().get(0), &[0.5, 1.5]);
+    }
+
     #[test]
     fn read_csv() {
         let path = PathBuf::from("test_data/test-25p.csv");
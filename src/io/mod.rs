@@ -14,3 +14,48 @@ where
 
     fn read(&self, file: &PathBuf) -> Result<PointCollection<T>, Self::ErrorType>;
 }
+
+/// Trait for file writers, mirroring `PointReader`
+pub trait PointWriter<T>
+where
+    T: Float,
+{
+    type ErrorType;
+
+    fn write(&self, file: &PathBuf, points: &PointCollection<T>) -> Result<(), Self::ErrorType>;
+}
+
+/// A labelled table of string-formatted values, for command output that is not
+/// itself a point collection (e.g. summary statistics, similarity indices).
+#[derive(Debug, Clone)]
+pub struct ResultTable {
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl ResultTable {
+    /// Creates an empty table with the given column header.
+    pub fn new(header: Vec<String>) -> Self {
+        ResultTable {
+            header,
+            rows: Vec::new(),
+        }
+    }
+    /// Appends a row. Its length should match the header.
+    pub fn push_row(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+    pub fn header(&self) -> &[String] {
+        &self.header
+    }
+    pub fn rows(&self) -> &[Vec<String>] {
+        &self.rows
+    }
+}
+
+/// Trait for writers of generic result tables
+pub trait TableWriter {
+    type ErrorType;
+
+    fn write(&self, file: &PathBuf, table: &ResultTable) -> Result<(), Self::ErrorType>;
+}
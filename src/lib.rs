@@ -0,0 +1,5 @@
+//! Point pattern analysis library
+pub mod cli;
+pub mod command;
+pub mod data;
+pub mod io;
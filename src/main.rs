@@ -1,17 +1,11 @@
 use ppa::cli::Cli;
-use std::{env, fs};
+use std::{env, fs, process};
 use structopt::StructOpt;
 
 fn main() {
-    let test = false;
+    let args: Vec<String> = env::args().collect();
 
-    let args: Vec<String> = if test {
-        vec!["ppa".to_string(), "jaccard".to_string()]
-    } else {
-        env::args().collect()
-    };
-
-    let _args: Cli = if args.len() == 2 && !args[1].starts_with('-') {
+    let cli: Cli = if args.len() == 2 && !args[1].starts_with('-') {
         let mut content = fs::read_to_string(&args[1]).expect(&format!(
             "Something went wrong reading the options file {:?}",
             &args[1]
@@ -21,4 +15,10 @@ fn main() {
     } else {
         Cli::from_args()
     };
+
+    let mut command = cli.build_command();
+    if let Err(err) = command.execute() {
+        eprintln!("Error: {}", err);
+        process::exit(1);
+    }
 }